@@ -1,179 +1,678 @@
-use cgmath::{Matrix4, PerspectiveFov, Rad, Vector3};
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use cgmath::{Matrix, Matrix4, PerspectiveFov, Rad, SquareMatrix, Vector3};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
-use web_sys::{WebGlBuffer, WebGlProgram, WebGlRenderingContext, WebGlShader};
+use web_sys::{
+    HtmlImageElement, WebGl2RenderingContext, WebGlBuffer, WebGlProgram, WebGlShader,
+    WebGlTexture, WebGlUniformLocation, WebGlVertexArrayObject,
+};
 
 const CANVAS_HEIGHT: u32 = 600;
 const CANVAS_WIDTH: u32 = 600;
 
+// Radians per second that the model rotates around the y/z axes.
+const ROTATION_SPEED: f32 = 1.0;
+
+/// Errors surfaced from DOM lookups and WebGL setup, so failures reach the
+/// browser console instead of panicking the wasm module.
+#[derive(Debug)]
+pub enum WasmError {
+    DomElementMissing(String),
+    WebGlContextCreation(String),
+    ShaderCompilation(String),
+    ProgramLink(String),
+    BufferCreation(String),
+    AttribLocationMissing(String),
+    TextureCreation(String),
+}
+
+impl std::fmt::Display for WasmError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WasmError::DomElementMissing(id) => write!(f, "missing DOM element: {id}"),
+            WasmError::WebGlContextCreation(msg) => {
+                write!(f, "failed to create WebGL2 context: {msg}")
+            }
+            WasmError::ShaderCompilation(log) => write!(f, "shader compilation failed: {log}"),
+            WasmError::ProgramLink(log) => write!(f, "program link failed: {log}"),
+            WasmError::BufferCreation(msg) => write!(f, "buffer creation failed: {msg}"),
+            WasmError::AttribLocationMissing(name) => {
+                write!(f, "attribute location not found: {name}")
+            }
+            WasmError::TextureCreation(msg) => write!(f, "texture creation failed: {msg}"),
+        }
+    }
+}
+
+impl From<WasmError> for JsValue {
+    fn from(error: WasmError) -> Self {
+        JsValue::from_str(&error.to_string())
+    }
+}
+
+/// An RGBA clear color, matching the tutorial's `gl.clearColor(r, g, b, a)` call.
+pub struct Color4 {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+    pub a: f32,
+}
+
+/// Thin wrapper around a `WebGl2RenderingContext` exposing typed helpers for the
+/// shader/program/buffer setup every draw call needs.
+pub struct WebGl2 {
+    pub context: WebGl2RenderingContext,
+}
+
+impl WebGl2 {
+    pub fn from_context(context: WebGl2RenderingContext) -> Self {
+        WebGl2 { context }
+    }
+
+    pub fn clear(&self, color: Color4) {
+        self.context.clear_color(color.r, color.g, color.b, color.a);
+        self.context.clear_depth(1.0);
+        self.context.clear(
+            WebGl2RenderingContext::COLOR_BUFFER_BIT | WebGl2RenderingContext::DEPTH_BUFFER_BIT,
+        );
+    }
+
+    pub fn compile_shader(&self, shader_type: u32, source: &str) -> Result<WebGlShader, WasmError> {
+        let shader = self
+            .context
+            .create_shader(shader_type)
+            .ok_or_else(|| WasmError::ShaderCompilation(String::from("unable to create shader object")))?;
+        self.context.shader_source(&shader, source);
+        self.context.compile_shader(&shader);
+
+        if self
+            .context
+            .get_shader_parameter(&shader, WebGl2RenderingContext::COMPILE_STATUS)
+            .as_bool()
+            .unwrap_or(false)
+        {
+            Ok(shader)
+        } else {
+            Err(WasmError::ShaderCompilation(
+                self.context
+                    .get_shader_info_log(&shader)
+                    .unwrap_or_else(|| String::from("unknown error creating shader")),
+            ))
+        }
+    }
+
+    pub fn link_program(
+        &self,
+        vertex_shader: &WebGlShader,
+        fragment_shader: &WebGlShader,
+    ) -> Result<WebGlProgram, WasmError> {
+        let program = self
+            .context
+            .create_program()
+            .ok_or_else(|| WasmError::ProgramLink(String::from("unable to create program object")))?;
+
+        self.context.attach_shader(&program, vertex_shader);
+        self.context.attach_shader(&program, fragment_shader);
+        self.context.link_program(&program);
+
+        if self
+            .context
+            .get_program_parameter(&program, WebGl2RenderingContext::LINK_STATUS)
+            .as_bool()
+            .unwrap_or(false)
+        {
+            Ok(program)
+        } else {
+            Err(WasmError::ProgramLink(
+                self.context
+                    .get_program_info_log(&program)
+                    .unwrap_or_else(|| String::from("unknown error creating program object")),
+            ))
+        }
+    }
+
+    pub fn create_buffer(&self) -> Result<WebGlBuffer, WasmError> {
+        self.context
+            .create_buffer()
+            .ok_or_else(|| WasmError::BufferCreation(String::from("unable to create buffer")))
+    }
+}
+
+pub struct AttribLocations {
+    pub vertex_position: Option<u32>,
+    pub vertex_color: Option<u32>,
+    pub texture_coord: Option<u32>,
+    pub vertex_normal: Option<u32>,
+}
+
+pub struct UniformLocations {
+    pub projection_matrix: Option<WebGlUniformLocation>,
+    pub model_view_matrix: Option<WebGlUniformLocation>,
+    pub u_sampler: Option<WebGlUniformLocation>,
+    pub normal_matrix: Option<WebGlUniformLocation>,
+}
+
+pub struct ProgramInfo {
+    pub program: WebGlProgram,
+    pub attrib_locations: AttribLocations,
+    pub uniform_locations: UniformLocations,
+}
+
+impl ProgramInfo {
+    pub fn new(context: &WebGl2RenderingContext, program: WebGlProgram) -> Self {
+        let attrib_locations = AttribLocations {
+            vertex_position: attrib_location(context, &program, "aVertexPosition"),
+            vertex_color: attrib_location(context, &program, "aVertexColor"),
+            texture_coord: attrib_location(context, &program, "aTextureCoord"),
+            vertex_normal: attrib_location(context, &program, "aVertexNormal"),
+        };
+
+        let uniform_locations = UniformLocations {
+            projection_matrix: context.get_uniform_location(&program, "uProjectionMatrix"),
+            model_view_matrix: context.get_uniform_location(&program, "uModelViewMatrix"),
+            u_sampler: context.get_uniform_location(&program, "uSampler"),
+            normal_matrix: context.get_uniform_location(&program, "uNormalMatrix"),
+        };
+
+        ProgramInfo {
+            program,
+            attrib_locations,
+            uniform_locations,
+        }
+    }
+}
+
+fn attrib_location(
+    context: &WebGl2RenderingContext,
+    program: &WebGlProgram,
+    name: &str,
+) -> Option<u32> {
+    let location = context.get_attrib_location(program, name);
+    if location < 0 {
+        None
+    } else {
+        Some(location as u32)
+    }
+}
+
+/// The position/color/texture-coordinate/normal/index buffers plus the VAO that records how they're bound to
+/// the shader's attributes, so `draw_scene` only has to rebind one object per frame.
+pub struct Buffers {
+    pub position: WebGlBuffer,
+    pub color: WebGlBuffer,
+    pub texture_coord: WebGlBuffer,
+    pub normal: WebGlBuffer,
+    pub indices: WebGlBuffer,
+    pub vao: WebGlVertexArrayObject,
+}
+
 #[wasm_bindgen(start)]
 pub fn start() -> Result<(), JsValue> {
-    let document = web_sys::window().unwrap().document().unwrap();
-    let canvas = document.get_element_by_id("canvas").unwrap();
-    // let canvas: web_sys::HtmlCanvasElement = canvas.dyn_into::<web_sys::HtmlCanvasElement>()?;
-    let canvas: web_sys::HtmlCanvasElement = canvas
-        .dyn_into::<web_sys::HtmlCanvasElement>()
-        .map_err(|_| ())
-        .unwrap();
+    let window = web_sys::window()
+        .ok_or_else(|| WasmError::DomElementMissing(String::from("window")))?;
+    let document = window
+        .document()
+        .ok_or_else(|| WasmError::DomElementMissing(String::from("document")))?;
+    let canvas = document
+        .get_element_by_id("canvas")
+        .ok_or_else(|| WasmError::DomElementMissing(String::from("canvas")))?;
+    let canvas: web_sys::HtmlCanvasElement = canvas.dyn_into::<web_sys::HtmlCanvasElement>().map_err(
+        |_| WasmError::DomElementMissing(String::from("canvas is not an HtmlCanvasElement")),
+    )?;
 
     canvas.set_height(CANVAS_HEIGHT);
     canvas.set_width(CANVAS_WIDTH);
 
     let context = canvas
-        .get_context("webgl")
-        .unwrap()
-        .unwrap()
-        .dyn_into::<WebGlRenderingContext>()
-        .unwrap();
+        .get_context("webgl2")
+        .map_err(|_| WasmError::WebGlContextCreation(String::from("getContext(\"webgl2\") threw")))?
+        .ok_or_else(|| {
+            WasmError::WebGlContextCreation(String::from("webgl2 is not supported"))
+        })?
+        .dyn_into::<WebGl2RenderingContext>()
+        .map_err(|_| {
+            WasmError::WebGlContextCreation(String::from(
+                "getContext(\"webgl2\") did not return a WebGl2RenderingContext",
+            ))
+        })?;
+    let gl = WebGl2::from_context(context);
 
     // Set clear color to black, fully opaque
-    context.clear_color(0.0, 0.0, 0.0, 1.0);
-    // Clear the color buffer with specified clear color
-    context.clear(WebGlRenderingContext::COLOR_BUFFER_BIT);
+    gl.clear(Color4 {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 1.0,
+    });
 
-    let vs_source = r#"
-        attribute vec4 aVertexPosition;
+    let vs_source = r#"#version 300 es
+        in vec4 aVertexPosition;
+        in vec4 aVertexColor;
+        in vec2 aTextureCoord;
+        in vec3 aVertexNormal;
 
         uniform mat4 uModelViewMatrix;
         uniform mat4 uProjectionMatrix;
+        uniform mat4 uNormalMatrix;
+
+        out lowp vec4 vColor;
+        out highp vec2 vTextureCoord;
+        out highp vec3 vLighting;
 
         void main() {
         gl_Position = uProjectionMatrix * uModelViewMatrix * aVertexPosition;
+        vColor = aVertexColor;
+        vTextureCoord = aTextureCoord;
+
+        highp vec3 ambientLight = vec3(0.3, 0.3, 0.3);
+        highp vec3 directionalLightColor = vec3(1.0, 1.0, 1.0);
+        highp vec3 directionalVector = normalize(vec3(0.85, 0.8, 0.75));
+
+        highp vec4 transformedNormal = uNormalMatrix * vec4(aVertexNormal, 1.0);
+        highp float directional = max(dot(transformedNormal.xyz, directionalVector), 0.0);
+        vLighting = ambientLight + (directionalLightColor * directional);
         }
     "#;
-    let fs_source = r#"
+    let fs_source = r#"#version 300 es
+        in lowp vec4 vColor;
+        in highp vec2 vTextureCoord;
+        in highp vec3 vLighting;
+
+        uniform sampler2D uSampler;
+
+        out lowp vec4 fragColor;
+
         void main() {
-            gl_FragColor = vec4(1.0, 1.0, 1.0, 1.0);
+            highp vec4 texelColor = texture(uSampler, vTextureCoord) * vColor;
+            fragColor = vec4(texelColor.rgb * vLighting, texelColor.a);
         }
     "#;
 
-    let shader_program = init_shader_program(&context, vs_source, fs_source)?;
-    context.use_program(Some(&shader_program));
+    let shader_program = init_shader_program(&gl, vs_source, fs_source)?;
+    let program_info = ProgramInfo::new(&gl.context, shader_program);
+    gl.context.use_program(Some(&program_info.program));
+
+    let buffers = init_buffers(&gl, &program_info)?;
+    let texture = load_texture(&gl, "assets/cube-texture.png")?;
+
+    let start_time: Rc<RefCell<Option<f64>>> = Rc::new(RefCell::new(None));
+
+    let f: Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>> = Rc::new(RefCell::new(None));
+    let g = f.clone();
+    let closure_window = window.clone();
+
+    *g.borrow_mut() = Some(Closure::new(move |timestamp: f64| {
+        let elapsed_seconds = {
+            let mut start = start_time.borrow_mut();
+            let start_ts = *start.get_or_insert(timestamp);
+            ((timestamp - start_ts) / 1000.0) as f32
+        };
+
+        let rotation = Rad(elapsed_seconds * ROTATION_SPEED);
+        let model_view_matrix = Matrix4::from_translation(Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: -6.0,
+        }) * Matrix4::from_angle_z(rotation)
+            * Matrix4::from_angle_y(rotation);
+
+        if let Err(error) = draw_scene(&gl, &buffers, &program_info, &texture, model_view_matrix) {
+            web_sys::console::error_1(&JsValue::from_str(&format!(
+                "Error drawing scene: {error}"
+            )));
+        }
 
-    let buffers = init_buffers(&context)?;
+        request_animation_frame(&closure_window, f.borrow().as_ref().unwrap());
+    }));
 
-    if draw_scene(&context, buffers, &shader_program).is_ok() {
-        Ok(())
-    } else {
-        Err(JsValue::from_str("Error"))
-    }
+    request_animation_frame(&window, g.borrow().as_ref().unwrap());
+
+    Ok(())
 }
 
-pub fn load_shader(
-    context: &WebGlRenderingContext,
-    shader_type: u32,
-    source: &str,
-) -> Result<WebGlShader, String> {
-    let shader = context
-        .create_shader(shader_type)
-        .ok_or_else(|| String::from("Unable to create shader object"))?;
-    context.shader_source(&shader, source);
-    context.compile_shader(&shader);
-
-    if context
-        .get_shader_parameter(&shader, WebGlRenderingContext::COMPILE_STATUS)
-        .as_bool()
-        .unwrap_or(false)
-    {
-        Ok(shader)
-    } else {
-        Err(context
-            .get_shader_info_log(&shader)
-            .unwrap_or_else(|| String::from("Unknown error creating shader")))
-    }
+fn request_animation_frame(window: &web_sys::Window, f: &Closure<dyn FnMut(f64)>) -> i32 {
+    window
+        .request_animation_frame(f.as_ref().unchecked_ref())
+        .expect("should register `requestAnimationFrame` OK")
 }
 
 pub fn init_shader_program(
-    context: &WebGlRenderingContext,
+    gl: &WebGl2,
     vert_shader: &str,
     frag_shader: &str,
-) -> Result<WebGlProgram, String> {
-    let vertex_shader = load_shader(&context, WebGlRenderingContext::VERTEX_SHADER, &vert_shader)?;
-    let fragment_shader = load_shader(
-        &context,
-        WebGlRenderingContext::FRAGMENT_SHADER,
-        &frag_shader,
-    )?;
+) -> Result<WebGlProgram, WasmError> {
+    let vertex_shader = gl.compile_shader(WebGl2RenderingContext::VERTEX_SHADER, vert_shader)?;
+    let fragment_shader =
+        gl.compile_shader(WebGl2RenderingContext::FRAGMENT_SHADER, frag_shader)?;
 
-    let shader_program = context
-        .create_program()
-        .ok_or_else(|| String::from("Unable to create shader object"))?;
+    gl.link_program(&vertex_shader, &fragment_shader)
+}
 
-    context.attach_shader(&shader_program, &vertex_shader);
-    context.attach_shader(&shader_program, &fragment_shader);
-    context.link_program(&shader_program);
+pub fn init_buffers(gl: &WebGl2, program_info: &ProgramInfo) -> Result<Buffers, WasmError> {
+    let vao = gl.context.create_vertex_array().ok_or_else(|| {
+        WasmError::BufferCreation(String::from("unable to create vertex array object"))
+    })?;
+    gl.context.bind_vertex_array(Some(&vao));
 
-    if context
-        .get_program_parameter(&shader_program, WebGlRenderingContext::LINK_STATUS)
-        .as_bool()
-        .unwrap_or(false)
-    {
-        Ok(shader_program)
-    } else {
-        Err(context
-            .get_program_info_log(&shader_program)
-            .unwrap_or_else(|| String::from("Unknown error creating program object")))
-    }
-}
+    const VAP_TYPE: u32 = WebGl2RenderingContext::FLOAT;
+    const NORMALIZE: bool = false;
+    const STRIDE: i32 = 0;
+    const OFFSET: i32 = 0;
 
-pub fn init_buffers(context: &WebGlRenderingContext) -> Result<(WebGlBuffer, WebGlBuffer), String> {
-    let position_buffer = context.create_buffer();
-    if position_buffer.is_none() {
-        return Err(String::from("Error creating color buffer"));
-    }
-    context.bind_buffer(
-        WebGlRenderingContext::ARRAY_BUFFER,
-        position_buffer.as_ref(),
+    #[rustfmt::skip]
+    let positions: [f32; 72] = [
+        // Front face
+        -1.0, -1.0, 1.0, 1.0, -1.0, 1.0, 1.0, 1.0, 1.0, -1.0, 1.0, 1.0,
+        // Back face
+        -1.0, -1.0, -1.0, -1.0, 1.0, -1.0, 1.0, 1.0, -1.0, 1.0, -1.0, -1.0,
+        // Top face
+        -1.0, 1.0, -1.0, -1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, -1.0,
+        // Bottom face
+        -1.0, -1.0, -1.0, 1.0, -1.0, -1.0, 1.0, -1.0, 1.0, -1.0, -1.0, 1.0,
+        // Right face
+        1.0, -1.0, -1.0, 1.0, 1.0, -1.0, 1.0, 1.0, 1.0, 1.0, -1.0, 1.0,
+        // Left face
+        -1.0, -1.0, -1.0, -1.0, -1.0, 1.0, -1.0, 1.0, 1.0, -1.0, 1.0, -1.0,
+    ];
+
+    let position_buffer = gl.create_buffer()?;
+    gl.context.bind_buffer(
+        WebGl2RenderingContext::ARRAY_BUFFER,
+        Some(&position_buffer),
     );
-    let positions: [f32; 8] = [-1.0, 1.0, 1.0, 1.0, -1.0, -1.0, 1.0, -1.0];
     unsafe {
         let vert_array = js_sys::Float32Array::view(&positions);
 
-        context.buffer_data_with_array_buffer_view(
-            WebGlRenderingContext::ARRAY_BUFFER,
+        gl.context.buffer_data_with_array_buffer_view(
+            WebGl2RenderingContext::ARRAY_BUFFER,
             &vert_array,
-            WebGlRenderingContext::STATIC_DRAW,
+            WebGl2RenderingContext::STATIC_DRAW,
         );
     }
 
-    let colors: [f32; 16] = [
-        1.0, 1.0, 1.0, 1.0, // white
-        1.0, 0.0, 0.0, 1.0, // red
-        0.0, 1.0, 0.0, 1.0, // green
-        0.0, 0.0, 1.0, 1.0, // blue
+    const POSITION_NUM_COMPONENTS: i32 = 3;
+    let vertex_position = program_info
+        .attrib_locations
+        .vertex_position
+        .ok_or_else(|| WasmError::AttribLocationMissing(String::from("aVertexPosition")))?;
+    gl.context.vertex_attrib_pointer_with_i32(
+        vertex_position,
+        POSITION_NUM_COMPONENTS,
+        VAP_TYPE,
+        NORMALIZE,
+        STRIDE,
+        OFFSET,
+    );
+    gl.context.enable_vertex_attrib_array(vertex_position);
+
+    #[rustfmt::skip]
+    let colors: [f32; 96] = [
+        // Front: white
+        1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0, 1.0,
+        // Back: red
+        1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0,
+        // Top: green
+        0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0, 0.0, 1.0,
+        // Bottom: blue
+        0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 1.0,
+        // Right: yellow
+        1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0,
+        // Left: purple
+        1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0, 1.0, 0.0, 1.0, 1.0,
     ];
 
-    let color_buffer = context.create_buffer();
-    if color_buffer.is_none() {
-        return Err(String::from("Error creating position buffer"));
-    }
-    context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, color_buffer.as_ref());
+    let color_buffer = gl.create_buffer()?;
+    gl.context
+        .bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&color_buffer));
     unsafe {
         let vert_array = js_sys::Float32Array::view(&colors);
 
-        context.buffer_data_with_array_buffer_view(
-            WebGlRenderingContext::ARRAY_BUFFER,
+        gl.context.buffer_data_with_array_buffer_view(
+            WebGl2RenderingContext::ARRAY_BUFFER,
             &vert_array,
-            WebGlRenderingContext::STATIC_DRAW,
+            WebGl2RenderingContext::STATIC_DRAW,
+        );
+    }
+
+    const COLOR_NUM_COMPONENTS: i32 = 4;
+    let vertex_color = program_info
+        .attrib_locations
+        .vertex_color
+        .ok_or_else(|| WasmError::AttribLocationMissing(String::from("aVertexColor")))?;
+    gl.context.vertex_attrib_pointer_with_i32(
+        vertex_color,
+        COLOR_NUM_COMPONENTS,
+        VAP_TYPE,
+        NORMALIZE,
+        STRIDE,
+        OFFSET,
+    );
+    gl.context.enable_vertex_attrib_array(vertex_color);
+
+    #[rustfmt::skip]
+    let texture_coordinates: [f32; 48] = [
+        // Front, back, top, bottom, right, left
+        0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0,
+        0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0,
+        0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0,
+        0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0,
+        0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0,
+        0.0, 0.0, 1.0, 0.0, 1.0, 1.0, 0.0, 1.0,
+    ];
+
+    let texture_coord_buffer = gl.create_buffer()?;
+    gl.context.bind_buffer(
+        WebGl2RenderingContext::ARRAY_BUFFER,
+        Some(&texture_coord_buffer),
+    );
+    unsafe {
+        let tex_coord_array = js_sys::Float32Array::view(&texture_coordinates);
+
+        gl.context.buffer_data_with_array_buffer_view(
+            WebGl2RenderingContext::ARRAY_BUFFER,
+            &tex_coord_array,
+            WebGl2RenderingContext::STATIC_DRAW,
+        );
+    }
+
+    const TEXTURE_COORD_NUM_COMPONENTS: i32 = 2;
+    let texture_coord = program_info
+        .attrib_locations
+        .texture_coord
+        .ok_or_else(|| WasmError::AttribLocationMissing(String::from("aTextureCoord")))?;
+    gl.context.vertex_attrib_pointer_with_i32(
+        texture_coord,
+        TEXTURE_COORD_NUM_COMPONENTS,
+        VAP_TYPE,
+        NORMALIZE,
+        STRIDE,
+        OFFSET,
+    );
+    gl.context.enable_vertex_attrib_array(texture_coord);
+
+    #[rustfmt::skip]
+    let normals: [f32; 72] = [
+        // Front
+        0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0,
+        // Back
+        0.0, 0.0, -1.0, 0.0, 0.0, -1.0, 0.0, 0.0, -1.0, 0.0, 0.0, -1.0,
+        // Top
+        0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+        // Bottom
+        0.0, -1.0, 0.0, 0.0, -1.0, 0.0, 0.0, -1.0, 0.0, 0.0, -1.0, 0.0,
+        // Right
+        1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0,
+        // Left
+        -1.0, 0.0, 0.0, -1.0, 0.0, 0.0, -1.0, 0.0, 0.0, -1.0, 0.0, 0.0,
+    ];
+
+    let normal_buffer = gl.create_buffer()?;
+    gl.context
+        .bind_buffer(WebGl2RenderingContext::ARRAY_BUFFER, Some(&normal_buffer));
+    unsafe {
+        let normal_array = js_sys::Float32Array::view(&normals);
+
+        gl.context.buffer_data_with_array_buffer_view(
+            WebGl2RenderingContext::ARRAY_BUFFER,
+            &normal_array,
+            WebGl2RenderingContext::STATIC_DRAW,
+        );
+    }
+
+    const NORMAL_NUM_COMPONENTS: i32 = 3;
+    let vertex_normal = program_info
+        .attrib_locations
+        .vertex_normal
+        .ok_or_else(|| WasmError::AttribLocationMissing(String::from("aVertexNormal")))?;
+    gl.context.vertex_attrib_pointer_with_i32(
+        vertex_normal,
+        NORMAL_NUM_COMPONENTS,
+        VAP_TYPE,
+        NORMALIZE,
+        STRIDE,
+        OFFSET,
+    );
+    gl.context.enable_vertex_attrib_array(vertex_normal);
+
+    #[rustfmt::skip]
+    let indices: [u16; 36] = [
+        0, 1, 2, 0, 2, 3, // front
+        4, 5, 6, 4, 6, 7, // back
+        8, 9, 10, 8, 10, 11, // top
+        12, 13, 14, 12, 14, 15, // bottom
+        16, 17, 18, 16, 18, 19, // right
+        20, 21, 22, 20, 22, 23, // left
+    ];
+
+    let index_buffer = gl.create_buffer()?;
+    gl.context.bind_buffer(
+        WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER,
+        Some(&index_buffer),
+    );
+    unsafe {
+        let index_array = js_sys::Uint16Array::view(&indices);
+
+        gl.context.buffer_data_with_array_buffer_view(
+            WebGl2RenderingContext::ELEMENT_ARRAY_BUFFER,
+            &index_array,
+            WebGl2RenderingContext::STATIC_DRAW,
         );
     }
 
-    return Ok((position_buffer.unwrap(), color_buffer.unwrap()));
+    gl.context.bind_vertex_array(None);
+
+    Ok(Buffers {
+        position: position_buffer,
+        color: color_buffer,
+        texture_coord: texture_coord_buffer,
+        normal: normal_buffer,
+        indices: index_buffer,
+        vao,
+    })
+}
+
+/// Creates a texture, uploads a 1x1 placeholder pixel so the cube can be drawn
+/// right away, then swaps in the real image once it finishes loading from `url`.
+pub fn load_texture(gl: &WebGl2, url: &str) -> Result<WebGlTexture, WasmError> {
+    let texture = gl
+        .context
+        .create_texture()
+        .ok_or_else(|| WasmError::TextureCreation(String::from("unable to create texture object")))?;
+    gl.context
+        .bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture));
+
+    const LEVEL: i32 = 0;
+    const INTERNAL_FORMAT: i32 = WebGl2RenderingContext::RGBA as i32;
+    const SRC_FORMAT: u32 = WebGl2RenderingContext::RGBA;
+    const SRC_TYPE: u32 = WebGl2RenderingContext::UNSIGNED_BYTE;
+    let placeholder_pixel: [u8; 4] = [0, 0, 255, 255];
+    gl.context
+        .tex_image_2d_with_i32_and_i32_and_i32_and_format_and_type_and_opt_u8_array(
+            WebGl2RenderingContext::TEXTURE_2D,
+            LEVEL,
+            INTERNAL_FORMAT,
+            1,
+            1,
+            0,
+            SRC_FORMAT,
+            SRC_TYPE,
+            Some(&placeholder_pixel),
+        )
+        .map_err(|_| WasmError::TextureCreation(String::from("unable to upload placeholder texture")))?;
+
+    let image = HtmlImageElement::new()
+        .map_err(|_| WasmError::TextureCreation(String::from("unable to create image element")))?;
+
+    let context = gl.context.clone();
+    let texture_handle = texture.clone();
+    let image_handle = image.clone();
+    let onload = Closure::<dyn FnMut()>::new(move || {
+        context.bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(&texture_handle));
+
+        if context
+            .tex_image_2d_with_u32_and_u32_and_html_image_element(
+                WebGl2RenderingContext::TEXTURE_2D,
+                LEVEL,
+                INTERNAL_FORMAT,
+                SRC_FORMAT,
+                SRC_TYPE,
+                &image_handle,
+            )
+            .is_err()
+        {
+            web_sys::console::error_1(&JsValue::from_str("Unable to upload texture image"));
+            return;
+        }
+
+        if is_power_of_2(image_handle.width()) && is_power_of_2(image_handle.height()) {
+            context.generate_mipmap(WebGl2RenderingContext::TEXTURE_2D);
+        } else {
+            context.tex_parameteri(
+                WebGl2RenderingContext::TEXTURE_2D,
+                WebGl2RenderingContext::TEXTURE_WRAP_S,
+                WebGl2RenderingContext::CLAMP_TO_EDGE as i32,
+            );
+            context.tex_parameteri(
+                WebGl2RenderingContext::TEXTURE_2D,
+                WebGl2RenderingContext::TEXTURE_WRAP_T,
+                WebGl2RenderingContext::CLAMP_TO_EDGE as i32,
+            );
+            context.tex_parameteri(
+                WebGl2RenderingContext::TEXTURE_2D,
+                WebGl2RenderingContext::TEXTURE_MIN_FILTER,
+                WebGl2RenderingContext::LINEAR as i32,
+            );
+        }
+    });
+
+    image.set_onload(Some(onload.as_ref().unchecked_ref()));
+    image.set_src(url);
+    onload.forget();
+
+    Ok(texture)
+}
+
+fn is_power_of_2(value: u32) -> bool {
+    value != 0 && (value & (value - 1)) == 0
 }
 
 pub fn draw_scene(
-    context: &WebGlRenderingContext,
-    buffers: (WebGlBuffer, WebGlBuffer),
-    shader_program: &WebGlProgram,
-) -> Result<(), String> {
-    context.clear_color(0.0, 0.0, 0.0, 1.0);
-    context.clear_depth(1.0);
-    context.enable(WebGlRenderingContext::DEPTH_TEST);
-    context.depth_func(WebGlRenderingContext::LEQUAL);
-
-    // context.clear_color(0.0, 0.0, 0.0, 1.0);
-    context.clear(WebGlRenderingContext::COLOR_BUFFER_BIT);
-    context.clear(WebGlRenderingContext::DEPTH_BUFFER_BIT);
+    gl: &WebGl2,
+    buffers: &Buffers,
+    program_info: &ProgramInfo,
+    texture: &WebGlTexture,
+    mut model_view_matrix: Matrix4<f32>,
+) -> Result<(), WasmError> {
+    gl.clear(Color4 {
+        r: 0.0,
+        g: 0.0,
+        b: 0.0,
+        a: 1.0,
+    });
+    gl.context.enable(WebGl2RenderingContext::DEPTH_TEST);
+    gl.context.depth_func(WebGl2RenderingContext::LEQUAL);
 
     const FIELD_OF_VIEW: Rad<f32> = Rad {
         0: 45.0 * std::f32::consts::PI / 180.0,
@@ -189,53 +688,51 @@ pub fn draw_scene(
         far: Z_FAR,
     };
 
-    let mut model_view_matrix: Matrix4<f32> = Matrix4::from_translation(Vector3 {
-        x: -0.0,
-        y: 0.0,
-        z: -6.0,
-    });
+    gl.context.bind_vertex_array(Some(&buffers.vao));
+    gl.context.use_program(Some(&program_info.program));
 
-    const NUM_COMPONENTS: i32 = 2;
-    const VAP_TYPE: u32 = WebGlRenderingContext::FLOAT;
-    const NORMALIZE: bool = false;
-    const STRIDE: i32 = 0;
-    const OFFSET: i32 = 0;
-
-    context.bind_buffer(WebGlRenderingContext::ARRAY_BUFFER, Some(buffers.0).as_ref());
-    let vertex_position = context.get_attrib_location(shader_program, "aVertexPosition") as u32;
-    context.vertex_attrib_pointer_with_i32(
-        vertex_position,
-        NUM_COMPONENTS,
-        VAP_TYPE,
-        NORMALIZE,
-        STRIDE,
-        OFFSET,
-    );
-    context.enable_vertex_attrib_array(vertex_position);
-
-    context.use_program(Some(shader_program));
-
-    let program_projection_matrix =
-        context.get_uniform_location(shader_program, "uProjectionMatrix");
     let mut projection_matrix = Matrix4::from(perspective_fov);
     let projection_matrix_slice: &[f32; 16] = &projection_matrix.as_mut();
-    context.uniform_matrix4fv_with_f32_array(
-        program_projection_matrix.as_ref(),
+    gl.context.uniform_matrix4fv_with_f32_array(
+        program_info.uniform_locations.projection_matrix.as_ref(),
         false,
         projection_matrix_slice,
     );
 
-    let program_model_view_matrix =
-        context.get_uniform_location(shader_program, "uModelViewMatrix");
     let model_view_matrix_slice: &[f32; 16] = &model_view_matrix.as_mut();
-    context.uniform_matrix4fv_with_f32_array(
-        program_model_view_matrix.as_ref(),
+    gl.context.uniform_matrix4fv_with_f32_array(
+        program_info.uniform_locations.model_view_matrix.as_ref(),
         false,
         model_view_matrix_slice,
     );
 
-    const VERTEX_COUNT: i32 = 4;
-    context.draw_arrays(WebGlRenderingContext::TRIANGLE_STRIP, OFFSET, VERTEX_COUNT);
+    let mut normal_matrix = model_view_matrix
+        .invert()
+        .unwrap_or(Matrix4::identity())
+        .transpose();
+    let normal_matrix_slice: &[f32; 16] = &normal_matrix.as_mut();
+    gl.context.uniform_matrix4fv_with_f32_array(
+        program_info.uniform_locations.normal_matrix.as_ref(),
+        false,
+        normal_matrix_slice,
+    );
+
+    gl.context.active_texture(WebGl2RenderingContext::TEXTURE0);
+    gl.context
+        .bind_texture(WebGl2RenderingContext::TEXTURE_2D, Some(texture));
+    gl.context
+        .uniform1i(program_info.uniform_locations.u_sampler.as_ref(), 0);
+
+    const OFFSET: i32 = 0;
+    const INDEX_COUNT: i32 = 36;
+    gl.context.draw_elements_with_i32(
+        WebGl2RenderingContext::TRIANGLES,
+        INDEX_COUNT,
+        WebGl2RenderingContext::UNSIGNED_SHORT,
+        OFFSET,
+    );
+
+    gl.context.bind_vertex_array(None);
 
     Ok(())
 }